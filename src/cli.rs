@@ -0,0 +1,71 @@
+use std::collections::HashSet;
+
+/// Command line options, resolved from argv. Style-valued fields hold the option's *current*
+/// resolved value: they start out as whatever `--flag value` set (if anything), and are updated
+/// in place as each option is resolved by [`crate::config::Config::from_args`], so that a later
+/// option's value function (e.g. `minus-non-emph-style` defaulting to `opt.minus_style`) observes
+/// the already-resolved value of an earlier one (e.g. `minus-style`), exactly as delta's builtin
+/// presets expect.
+#[derive(Clone, Debug, Default)]
+pub struct Opt {
+    pub minus_style: String,
+    pub minus_non_emph_style: String,
+    pub minus_emph_style: String,
+    pub zero_style: String,
+    pub plus_style: String,
+    pub plus_non_emph_style: String,
+    pub plus_emph_style: String,
+    pub commit_style: String,
+    pub commit_decoration_style: String,
+    pub file_style: String,
+    pub file_decoration_style: String,
+    pub hunk_header_style: String,
+    pub hunk_header_decoration_style: String,
+    pub navigate: bool,
+    pub line_numbers: bool,
+    pub side_by_side: bool,
+    pub light: bool,
+    pub dark: bool,
+    pub presets: Option<String>,
+    /// Names (in `--long-option` form, without the leading `--`) of options that were given
+    /// explicitly on the command line, and therefore take precedence over presets and git config.
+    pub explicit_options: HashSet<String>,
+}
+
+impl Opt {
+    /// Parse delta's two positional diff inputs followed by `--flag value` / `--flag` pairs. Only
+    /// the flags consulted by the preset-resolution path are recognised here; the rest of delta's
+    /// CLI surface is defined by the real argument parser.
+    pub fn from_args(args: &[&str]) -> Self {
+        let mut opt = Opt::default();
+        let mut i = 0;
+        while i < args.len() {
+            match args[i] {
+                "--minus-style" => {
+                    opt.minus_style = Self::value_after(args, &mut i);
+                    opt.explicit_options.insert("minus-style".to_string());
+                }
+                "--plus-style" => {
+                    opt.plus_style = Self::value_after(args, &mut i);
+                    opt.explicit_options.insert("plus-style".to_string());
+                }
+                "--presets" => {
+                    opt.presets = Some(Self::value_after(args, &mut i));
+                }
+                "--light" => opt.light = true,
+                "--dark" => opt.dark = true,
+                "--24-bit-color" => {
+                    let _ = Self::value_after(args, &mut i);
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        opt
+    }
+
+    fn value_after(args: &[&str], i: &mut usize) -> String {
+        *i += 1;
+        args.get(*i).map(|s| s.to_string()).unwrap_or_default()
+    }
+}
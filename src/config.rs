@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+
+use crate::cli;
+use crate::git_config::GitConfig;
+use crate::preset::{self, BuiltinPreset, GetValueFunctionFromBuiltinPreset};
+use crate::style::Style;
+
+/// The fully resolved set of display options delta renders with, after combining (in increasing
+/// order of precedence) builtin defaults, the main `[delta]` git config section, presets (with
+/// `delta.<name>.inherits` expanded transitively, left-to-right, last-listed-wins), and finally
+/// any option given explicitly on the command line.
+pub struct Config<'a> {
+    pub minus_style: Style,
+    pub minus_non_emph_style: Style,
+    pub minus_emph_style: Style,
+    pub zero_style: Style,
+    pub plus_style: Style,
+    pub plus_non_emph_style: Style,
+    pub plus_emph_style: Style,
+    pub commit_style: Style,
+    pub file_style: Style,
+    pub hunk_header_style: Style,
+    pub navigate: bool,
+    pub line_numbers: bool,
+    pub side_by_side: bool,
+    _marker: std::marker::PhantomData<&'a ()>,
+}
+
+impl<'a> Config<'a> {
+    pub fn from_args(args: &[&str], git_config: &mut Option<GitConfig>) -> Self {
+        let mut opt = cli::Opt::from_args(args);
+        let builtin_presets = preset::make_builtin_presets();
+
+        // This is the integration point the preset-inheritance feature hangs off: expand
+        // `--presets` (a space-separated list of builtin and/or user-defined preset names) by
+        // resolving each name's `delta.<name>.inherits` git config key transitively, before
+        // applying the resulting, still left-to-right, list of presets' overrides below.
+        let preset_names = match &opt.presets {
+            Some(presets) => {
+                preset::expand_presets_with_inheritance(presets, &*git_config).unwrap_or_else(|err| {
+                    eprintln!("{}", err);
+                    Vec::new()
+                })
+            }
+            None => Vec::new(),
+        };
+
+        macro_rules! resolve_string {
+            ($option_name:expr) => {
+                resolve_string_option($option_name, &opt, &*git_config, &preset_names, &builtin_presets)
+            };
+        }
+        macro_rules! resolve_bool {
+            ($option_name:expr) => {
+                resolve_bool_option($option_name, &opt, &*git_config, &preset_names, &builtin_presets)
+            };
+        }
+
+        let minus_style_raw = resolve_string!("minus-style");
+        opt.minus_style = minus_style_raw.clone();
+        let minus_style = Style::from_str(&minus_style_raw, None, None, None, false, false);
+
+        let minus_non_emph_style_raw = resolve_string!("minus-non-emph-style");
+        opt.minus_non_emph_style = minus_non_emph_style_raw.clone();
+        let minus_non_emph_style =
+            Style::from_str(&minus_non_emph_style_raw, None, None, None, false, false);
+
+        let minus_emph_style_raw = resolve_string!("minus-emph-style");
+        opt.minus_emph_style = minus_emph_style_raw.clone();
+        let minus_emph_style = Style::from_str(&minus_emph_style_raw, None, None, None, true, true);
+
+        let zero_style_raw = resolve_string!("zero-style");
+        opt.zero_style = zero_style_raw.clone();
+        let zero_style = Style::from_str(&zero_style_raw, None, None, None, false, false);
+
+        let plus_style_raw = resolve_string!("plus-style");
+        opt.plus_style = plus_style_raw.clone();
+        let plus_style = Style::from_str(&plus_style_raw, None, None, None, false, false);
+
+        let plus_non_emph_style_raw = resolve_string!("plus-non-emph-style");
+        opt.plus_non_emph_style = plus_non_emph_style_raw.clone();
+        let plus_non_emph_style =
+            Style::from_str(&plus_non_emph_style_raw, None, None, None, false, false);
+
+        let plus_emph_style_raw = resolve_string!("plus-emph-style");
+        opt.plus_emph_style = plus_emph_style_raw.clone();
+        let plus_emph_style = Style::from_str(&plus_emph_style_raw, None, None, None, true, true);
+
+        let commit_style_raw = resolve_string!("commit-style");
+        opt.commit_style = commit_style_raw.clone();
+        let commit_decoration_style_raw = resolve_string!("commit-decoration-style");
+        opt.commit_decoration_style = commit_decoration_style_raw.clone();
+        let mut commit_style = Style::from_str(&commit_style_raw, None, None, None, false, false);
+        commit_style.decoration_style =
+            crate::style::DecorationStyle::from_str(&commit_decoration_style_raw, true);
+
+        let file_style_raw = resolve_string!("file-style");
+        opt.file_style = file_style_raw.clone();
+        let file_decoration_style_raw = resolve_string!("file-decoration-style");
+        opt.file_decoration_style = file_decoration_style_raw.clone();
+        let mut file_style = Style::from_str(&file_style_raw, None, None, None, false, false);
+        file_style.decoration_style =
+            crate::style::DecorationStyle::from_str(&file_decoration_style_raw, true);
+
+        let hunk_header_style_raw = resolve_string!("hunk-header-style");
+        opt.hunk_header_style = hunk_header_style_raw.clone();
+        let hunk_header_decoration_style_raw = resolve_string!("hunk-header-decoration-style");
+        opt.hunk_header_decoration_style = hunk_header_decoration_style_raw.clone();
+        let mut hunk_header_style =
+            Style::from_str(&hunk_header_style_raw, None, None, None, false, false);
+        hunk_header_style.decoration_style =
+            crate::style::DecorationStyle::from_str(&hunk_header_decoration_style_raw, true);
+
+        let navigate = resolve_bool!("navigate");
+        let line_numbers = resolve_bool!("line-numbers");
+        let side_by_side = resolve_bool!("side-by-side");
+
+        Config {
+            minus_style,
+            minus_non_emph_style,
+            minus_emph_style,
+            zero_style,
+            plus_style,
+            plus_non_emph_style,
+            plus_emph_style,
+            commit_style,
+            file_style,
+            hunk_header_style,
+            navigate,
+            line_numbers,
+            side_by_side,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+/// Resolve a single string-valued option: start from the main `[delta]` git config section,
+/// apply each preset in `preset_names` in order (builtin presets via their typed value function,
+/// user-defined presets via their `delta.<name>.<option>` git config key), then let an explicit
+/// command line flag win over all of that.
+fn resolve_string_option(
+    option_name: &str,
+    opt: &cli::Opt,
+    git_config: &Option<GitConfig>,
+    preset_names: &[String],
+    builtin_presets: &HashMap<String, BuiltinPreset>,
+) -> String {
+    let mut value = git_config
+        .as_ref()
+        .and_then(|git_config| git_config.get::<String>(&format!("delta.{}", option_name)))
+        .unwrap_or_default();
+
+    for preset_name in preset_names {
+        if let Some(builtin_preset) = builtin_presets.get(preset_name) {
+            if let Some(value_function) =
+                String::get_value_function_from_builtin_preset(option_name, builtin_preset)
+            {
+                value = value_function(opt, git_config);
+            }
+        } else if let Some(override_value) = git_config.as_ref().and_then(|git_config| {
+            git_config.get::<String>(&format!("delta.{}.{}", preset_name, option_name))
+        }) {
+            value = override_value;
+        }
+    }
+
+    if let Some(explicit_value) = explicit_string_value(opt, option_name) {
+        value = explicit_value;
+    }
+
+    value
+}
+
+fn resolve_bool_option(
+    option_name: &str,
+    opt: &cli::Opt,
+    git_config: &Option<GitConfig>,
+    preset_names: &[String],
+    builtin_presets: &HashMap<String, BuiltinPreset>,
+) -> bool {
+    let mut value = git_config
+        .as_ref()
+        .and_then(|git_config| git_config.get::<bool>(&format!("delta.{}", option_name)))
+        .unwrap_or(false);
+
+    for preset_name in preset_names {
+        if let Some(builtin_preset) = builtin_presets.get(preset_name) {
+            if let Some(value_function) =
+                bool::get_value_function_from_builtin_preset(option_name, builtin_preset)
+            {
+                value = value_function(opt, git_config);
+            }
+        } else if let Some(override_value) = git_config.as_ref().and_then(|git_config| {
+            git_config.get::<bool>(&format!("delta.{}.{}", preset_name, option_name))
+        }) {
+            value = override_value;
+        }
+    }
+
+    value
+}
+
+fn explicit_string_value(opt: &cli::Opt, option_name: &str) -> Option<String> {
+    if !opt.explicit_options.contains(option_name) {
+        return None;
+    }
+    match option_name {
+        "minus-style" => Some(opt.minus_style.clone()),
+        "plus-style" => Some(opt.plus_style.clone()),
+        _ => None,
+    }
+}
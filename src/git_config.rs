@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A minimal reader for the subset of the git-config text format delta needs: `[section]` /
+/// `[section "subsection"]` headers and `key = value` lines. Keys are looked up dotted, e.g.
+/// `color.diff.old` or `delta.my-theme.inherits`, matching the keys git itself would report for
+/// `color.diff.old` and `delta.my-theme.inherits` respectively.
+#[derive(Clone, Debug, Default)]
+pub struct GitConfig {
+    values: HashMap<String, String>,
+}
+
+impl GitConfig {
+    pub fn from_path(path: &Path) -> Self {
+        let contents = std::fs::read_to_string(path).unwrap_or_default();
+        Self::from_contents(&contents)
+    }
+
+    fn from_contents(contents: &str) -> Self {
+        let mut values = HashMap::new();
+        let mut section = String::new();
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            if line.starts_with('[') && line.ends_with(']') {
+                let inner = &line[1..line.len() - 1];
+                section = match inner.split_once(' ') {
+                    Some((name, subsection)) => {
+                        format!("{}.{}", name, subsection.trim().trim_matches('"'))
+                    }
+                    None => inner.to_string(),
+                };
+                continue;
+            }
+            if let Some((key, value)) = line.split_once('=') {
+                values.insert(
+                    format!("{}.{}", section, key.trim()),
+                    value.trim().to_string(),
+                );
+            }
+        }
+        GitConfig { values }
+    }
+
+    pub fn get<T: GitConfigGet>(&self, key: &str) -> Option<T> {
+        T::git_config_get(self.values.get(key)?)
+    }
+}
+
+pub trait GitConfigGet: Sized {
+    fn git_config_get(raw: &str) -> Option<Self>;
+}
+
+impl GitConfigGet for String {
+    fn git_config_get(raw: &str) -> Option<Self> {
+        Some(raw.to_string())
+    }
+}
+
+impl GitConfigGet for bool {
+    fn git_config_get(raw: &str) -> Option<Self> {
+        match raw.to_lowercase().as_str() {
+            "true" | "yes" | "on" | "1" => Some(true),
+            "false" | "no" | "off" | "0" => Some(false),
+            _ => None,
+        }
+    }
+}
+
+impl GitConfigGet for i64 {
+    fn git_config_get(raw: &str) -> Option<Self> {
+        raw.parse().ok()
+    }
+}
+
+impl GitConfigGet for usize {
+    fn git_config_get(raw: &str) -> Option<Self> {
+        raw.parse().ok()
+    }
+}
+
+impl GitConfigGet for f64 {
+    fn git_config_get(raw: &str) -> Option<Self> {
+        raw.parse().ok()
+    }
+}
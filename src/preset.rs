@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::cli;
 use crate::git_config::GitConfig;
@@ -14,14 +14,24 @@ use crate::git_config::GitConfig;
 /// A builtin preset is a named set of command line (option, value) pairs that is built in to
 /// delta. The implementation stores each value as a function, which allows the value (a) to depend
 /// dynamically on the value of other command line options, and (b) to be taken from git config.
-// Currently, all values in builtin presets are of type String.
-pub type BuiltinPreset<T> = HashMap<String, PresetValueFunction<T>>;
-type PresetValueFunction<T> = Box<dyn Fn(&cli::Opt, &Option<GitConfig>) -> T>;
+/// Builtin presets are not restricted to string-valued options: a preset's value functions are
+/// stored as a `PresetValueFunction`, an enum with one variant per supported option value type, so
+/// a single preset can set string, boolean, and numeric options together.
+pub type BuiltinPreset = HashMap<String, PresetValueFunction>;
+
+pub enum PresetValueFunction {
+    String(Box<dyn Fn(&cli::Opt, &Option<GitConfig>) -> String>),
+    OptionString(Box<dyn Fn(&cli::Opt, &Option<GitConfig>) -> Option<String>>),
+    Boolean(Box<dyn Fn(&cli::Opt, &Option<GitConfig>) -> bool>),
+    Int64(Box<dyn Fn(&cli::Opt, &Option<GitConfig>) -> i64>),
+    Usize(Box<dyn Fn(&cli::Opt, &Option<GitConfig>) -> usize>),
+    Float64(Box<dyn Fn(&cli::Opt, &Option<GitConfig>) -> f64>),
+}
 
 // Construct a 2-level hash map: (preset name) -> (option name) -> (value function). A value
 // function is a function that takes an Opt struct, and a git Config struct, and returns the value
 // for the option.
-pub fn make_builtin_presets() -> HashMap<String, BuiltinPreset<String>> {
+pub fn make_builtin_presets() -> HashMap<String, BuiltinPreset> {
     vec![
         (
             "diff-highlight".to_string(),
@@ -36,134 +46,297 @@ pub fn make_builtin_presets() -> HashMap<String, BuiltinPreset<String>> {
     .collect()
 }
 
+/// A preset section in git config may declare that it builds on other presets via
+///
+/// [delta "my-theme"]
+///     inherits = diff-so-fancy decorations
+///
+/// `inherits` takes the same space-separated-list syntax as `--presets`, and its entries may name
+/// either builtin or user-defined presets. This expands `preset_names` (again in `--presets`
+/// syntax) into the final, ordered list of preset names to apply: each name's `inherits` parents
+/// are expanded and inserted immediately before the name itself, transitively, before moving on to
+/// the next name in `preset_names`. Expanding parents first and preserving the relative order of
+/// `preset_names` keeps the existing precedence rule intact: when the same option is set by more
+/// than one preset, the last-listed one wins.
+///
+/// Cycles (a preset that inherits from itself, directly or transitively) are rejected rather than
+/// expanded forever. `visited` tracks only the names on the current inheritance *path* (the
+/// ancestors we're in the middle of expanding), not every name ever seen: a name is inserted
+/// before its parents are expanded and removed again once that's done, so a name reachable twice
+/// via independent branches (diamond inheritance) or simply repeated in `preset_names` is fine —
+/// only a name that inherits from itself, directly or transitively, is rejected.
+pub fn expand_presets_with_inheritance(
+    preset_names: &str,
+    git_config: &Option<GitConfig>,
+) -> Result<Vec<String>, String> {
+    let mut expanded = Vec::new();
+    let mut visited = HashSet::new();
+    for name in preset_names.split_whitespace() {
+        expand_preset(name, git_config, &mut visited, &mut expanded)?;
+    }
+    Ok(expanded)
+}
+
+fn expand_preset(
+    name: &str,
+    git_config: &Option<GitConfig>,
+    visited: &mut HashSet<String>,
+    expanded: &mut Vec<String>,
+) -> Result<(), String> {
+    if !visited.insert(name.to_string()) {
+        return Err(format!(
+            "delta: preset '{}' inherits from itself (directly or transitively)",
+            name
+        ));
+    }
+    if let Some(git_config) = git_config {
+        let inherits_key = format!("delta.{}.inherits", name);
+        if let Some(parents) = git_config.get::<String>(&inherits_key) {
+            for parent in parents.split_whitespace() {
+                expand_preset(parent, git_config, visited, expanded)?;
+            }
+        }
+    }
+    visited.remove(name);
+    expanded.push(name.to_string());
+    Ok(())
+}
+
 /// The macro permits the values of a builtin preset to be specified as either (a) a git config
 /// entry or (b) a value, which may be computed from the other command line options (cli::Opt).
+/// `$value_type` names the `PresetValueFunction` variant (and therefore the git-config type) that
+/// this entry resolves to, so a single preset can mix string, boolean, and numeric options.
 macro_rules! builtin_preset {
-    ([$( ($option_name:expr, $git_config_key:expr, $opt:ident => $value:expr) ),*]) => {
+    ([$( ($option_name:expr, $git_config_key:expr, $value_type:ident, $opt:ident => $value:expr) ),*]) => {
         vec![$(
             (
                 $option_name.to_string(),
-                Box::new(move |$opt: &cli::Opt, git_config: &Option<GitConfig>| {
+                PresetValueFunction::$value_type(Box::new(move |$opt: &cli::Opt, git_config: &Option<GitConfig>| {
                     match (git_config, $git_config_key) {
-                        (Some(git_config), Some(git_config_key)) => git_config.get::<String>(git_config_key),
+                        (Some(git_config), Some(git_config_key)) => git_config.get(git_config_key),
                         _ => None,
                     }
                     .unwrap_or_else(|| $value)
-                }) as PresetValueFunction<String>
+                }))
             )
         ),*]
     }
 }
 
-fn _make_diff_highlight_preset<'a>(bold: bool) -> Vec<(String, PresetValueFunction<String>)> {
+/// Whether builtin preset value functions should pick colors suited to a light terminal
+/// background rather than a dark one. This reads only the `--light` command line flag
+/// (`opt.light`): without it, including when `--dark` is given, the dark-background palette is
+/// used. The terminal's background is not detected here.
+fn is_light_mode(opt: &cli::Opt) -> bool {
+    opt.light
+}
+
+fn _make_diff_highlight_preset<'a>(bold: bool) -> Vec<(String, PresetValueFunction)> {
     builtin_preset!([
         (
             "minus-style",
             Some("color.diff.old"),
-            _opt => (if bold { "bold red" } else { "red" }).to_string()
+            String,
+            opt => match (is_light_mode(opt), bold) {
+                (false, false) => "red".to_string(),
+                (false, true) => "bold red".to_string(),
+                (true, false) => "88".to_string(),
+                (true, true) => "bold 88".to_string(),
+            }
         ),
         (
             "minus-non-emph-style",
             Some("color.diff-highlight.oldNormal"),
+            String,
             opt => opt.minus_style.clone()
         ),
         (
             "minus-emph-style",
             Some("color.diff-highlight.oldHighlight"),
+            String,
             opt => format!("{} reverse", opt.minus_style)
         ),
         (
             "zero-style",
             None,
+            String,
             _opt => "normal".to_string()
         ),
         (
             "plus-style",
             Some("color.diff.new"),
-            _opt => (if bold { "bold green" } else { "green" }).to_string()
+            String,
+            opt => match (is_light_mode(opt), bold) {
+                (false, false) => "green".to_string(),
+                (false, true) => "bold green".to_string(),
+                (true, false) => "28".to_string(),
+                (true, true) => "bold 28".to_string(),
+            }
         ),
         (
             "plus-non-emph-style",
             Some("color.diff-highlight.newNormal"),
+            String,
             opt => opt.plus_style.clone()
         ),
         (
             "plus-emph-style",
             Some("color.diff-highlight.newHighlight"),
+            String,
             opt => format!("{} reverse", opt.plus_style)
         )
     ])
 }
 
-fn make_diff_highlight_preset() -> Vec<(String, PresetValueFunction<String>)> {
+fn make_diff_highlight_preset() -> Vec<(String, PresetValueFunction)> {
     _make_diff_highlight_preset(false)
 }
 
-fn make_diff_so_fancy_preset() -> Vec<(String, PresetValueFunction<String>)> {
+fn make_diff_so_fancy_preset() -> Vec<(String, PresetValueFunction)> {
     let mut preset = _make_diff_highlight_preset(true);
     preset.extend(builtin_preset!([
         (
             "commit-style",
             None,
-            _opt => "bold yellow".to_string()
+            String,
+            opt => (if is_light_mode(opt) { "bold 94" } else { "bold yellow" }).to_string()
         ),
         (
             "commit-decoration-style",
             None,
+            String,
             _opt => "none".to_string()
         ),
         (
             "file-style",
             Some("color.diff.meta"),
+            String,
             _opt => "11".to_string()
         ),
         (
             "file-decoration-style",
             None,
-            _opt => "bold yellow ul ol".to_string()
+            String,
+            opt => (if is_light_mode(opt) { "bold 94 ul ol" } else { "bold yellow ul ol" }).to_string()
         ),
         (
             "hunk-header-style",
             Some("color.diff.frag"),
+            String,
             _opt => "bold syntax".to_string()
         ),
         (
             "hunk-header-decoration-style",
             None,
+            String,
             _opt => "magenta box".to_string()
+        ),
+        (
+            "navigate",
+            Some("delta.diff-so-fancy.navigate"),
+            Boolean,
+            _opt => true
+        ),
+        (
+            "line-numbers",
+            Some("delta.diff-so-fancy.line-numbers"),
+            Boolean,
+            _opt => true
+        ),
+        (
+            "side-by-side",
+            Some("delta.diff-so-fancy.side-by-side"),
+            Boolean,
+            _opt => false
         )
     ]));
     preset
 }
 
-// Currently the builtin presets only have String values. The trait is implemented for other types
-// out of necessity.
-pub trait GetValueFunctionFromBuiltinPreset {
+/// Each type that can be the value of a delta option implements this trait, so that, given an
+/// option name and a builtin preset, we can look up the value function for that option without
+/// the caller having to match on `PresetValueFunction` itself.
+pub trait GetValueFunctionFromBuiltinPreset
+where
+    Self: Sized,
+{
     fn get_value_function_from_builtin_preset<'a>(
-        _option_name: &str,
-        _builtin_preset: &'a BuiltinPreset<String>,
-    ) -> Option<&'a PresetValueFunction<Self>>
-    where
-        Self: Sized,
-    {
-        None
-    }
+        option_name: &str,
+        builtin_preset: &'a BuiltinPreset,
+    ) -> Option<&'a (dyn Fn(&cli::Opt, &Option<GitConfig>) -> Self)>;
 }
 
 impl GetValueFunctionFromBuiltinPreset for String {
     fn get_value_function_from_builtin_preset<'a>(
         option_name: &str,
-        builtin_preset: &'a BuiltinPreset<String>,
-    ) -> Option<&'a PresetValueFunction<String>> {
-        builtin_preset.get(option_name)
+        builtin_preset: &'a BuiltinPreset,
+    ) -> Option<&'a (dyn Fn(&cli::Opt, &Option<GitConfig>) -> Self)> {
+        match builtin_preset.get(option_name) {
+            Some(PresetValueFunction::String(f)) => Some(f.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl GetValueFunctionFromBuiltinPreset for Option<String> {
+    fn get_value_function_from_builtin_preset<'a>(
+        option_name: &str,
+        builtin_preset: &'a BuiltinPreset,
+    ) -> Option<&'a (dyn Fn(&cli::Opt, &Option<GitConfig>) -> Self)> {
+        match builtin_preset.get(option_name) {
+            Some(PresetValueFunction::OptionString(f)) => Some(f.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl GetValueFunctionFromBuiltinPreset for bool {
+    fn get_value_function_from_builtin_preset<'a>(
+        option_name: &str,
+        builtin_preset: &'a BuiltinPreset,
+    ) -> Option<&'a (dyn Fn(&cli::Opt, &Option<GitConfig>) -> Self)> {
+        match builtin_preset.get(option_name) {
+            Some(PresetValueFunction::Boolean(f)) => Some(f.as_ref()),
+            _ => None,
+        }
     }
 }
 
-impl GetValueFunctionFromBuiltinPreset for bool {}
-impl GetValueFunctionFromBuiltinPreset for i64 {}
-impl GetValueFunctionFromBuiltinPreset for usize {}
-impl GetValueFunctionFromBuiltinPreset for f64 {}
-impl GetValueFunctionFromBuiltinPreset for Option<String> {}
+impl GetValueFunctionFromBuiltinPreset for i64 {
+    fn get_value_function_from_builtin_preset<'a>(
+        option_name: &str,
+        builtin_preset: &'a BuiltinPreset,
+    ) -> Option<&'a (dyn Fn(&cli::Opt, &Option<GitConfig>) -> Self)> {
+        match builtin_preset.get(option_name) {
+            Some(PresetValueFunction::Int64(f)) => Some(f.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl GetValueFunctionFromBuiltinPreset for usize {
+    fn get_value_function_from_builtin_preset<'a>(
+        option_name: &str,
+        builtin_preset: &'a BuiltinPreset,
+    ) -> Option<&'a (dyn Fn(&cli::Opt, &Option<GitConfig>) -> Self)> {
+        match builtin_preset.get(option_name) {
+            Some(PresetValueFunction::Usize(f)) => Some(f.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl GetValueFunctionFromBuiltinPreset for f64 {
+    fn get_value_function_from_builtin_preset<'a>(
+        option_name: &str,
+        builtin_preset: &'a BuiltinPreset,
+    ) -> Option<&'a (dyn Fn(&cli::Opt, &Option<GitConfig>) -> Self)> {
+        match builtin_preset.get(option_name) {
+            Some(PresetValueFunction::Float64(f)) => Some(f.as_ref()),
+            _ => None,
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -362,6 +535,14 @@ mod tests {
         assert_eq!(config.plus_emph_style, make_emph_style("green reverse"));
     }
 
+    #[test]
+    fn test_diff_highlight_light_mode() {
+        let config = make_config(&["--presets", "diff-highlight", "--light"], None, None);
+
+        assert_eq!(config.minus_style, make_style("88"));
+        assert_eq!(config.plus_style, make_style("28"));
+    }
+
     #[test]
     fn test_diff_highlight_respects_gitconfig() {
         let git_config_contents = b"
@@ -424,6 +605,47 @@ mod tests {
             config.hunk_header_style.decoration_style,
             make_decoration_style("magenta box")
         );
+
+        assert_eq!(config.navigate, true);
+        assert_eq!(config.line_numbers, true);
+        assert_eq!(config.side_by_side, false);
+    }
+
+    #[test]
+    fn test_diff_so_fancy_light_mode() {
+        let config = make_config(
+            &["--presets", "diff-so-fancy", "--light"],
+            None,
+            None,
+        );
+
+        assert_eq!(
+            config.commit_style.ansi_term_style,
+            make_style("bold 94").ansi_term_style
+        );
+        assert_eq!(
+            config.file_style.decoration_style,
+            make_decoration_style("bold 94 ul ol")
+        );
+    }
+
+    #[test]
+    fn test_diff_so_fancy_boolean_options_respect_git_config() {
+        let git_config_contents = b"
+[delta \"diff-so-fancy\"]
+    side-by-side = true
+";
+        let git_config_path = "delta__test_diff_so_fancy_booleans.gitconfig";
+
+        let config = make_config(
+            &["--presets", "diff-so-fancy"],
+            Some(git_config_contents),
+            Some(git_config_path),
+        );
+
+        assert_eq!(config.side_by_side, true);
+
+        remove_file(git_config_path).unwrap();
     }
 
     #[test]
@@ -526,6 +748,137 @@ mod tests {
         remove_file(git_config_path).unwrap();
     }
 
+    #[test]
+    fn test_preset_inheritance() {
+        let git_config_contents = b"
+[delta \"decorations\"]
+    commit-decoration-style = bold box ul
+
+[delta \"my-theme\"]
+    inherits = diff-so-fancy decorations
+    file-style = bold 19 ul
+";
+        let git_config_path = "delta__test_preset_inheritance.gitconfig";
+        let git_config = Some(make_git_config(git_config_contents, git_config_path));
+
+        assert_eq!(
+            super::expand_presets_with_inheritance("my-theme", &git_config).unwrap(),
+            vec!["diff-so-fancy", "decorations", "my-theme"]
+        );
+
+        remove_file(git_config_path).unwrap();
+    }
+
+    #[test]
+    fn test_preset_inheritance_preserves_left_to_right_precedence() {
+        let git_config_contents = b"
+[delta \"a\"]
+    inherits = diff-so-fancy
+[delta \"b\"]
+    inherits = decorations
+";
+        let git_config_path =
+            "delta__test_preset_inheritance_preserves_left_to_right_precedence.gitconfig";
+        let git_config = Some(make_git_config(git_config_contents, git_config_path));
+
+        assert_eq!(
+            super::expand_presets_with_inheritance("a b", &git_config).unwrap(),
+            vec!["diff-so-fancy", "a", "decorations", "b"]
+        );
+
+        remove_file(git_config_path).unwrap();
+    }
+
+    #[test]
+    fn test_preset_inheritance_detects_cycles() {
+        let git_config_contents = b"
+[delta \"a\"]
+    inherits = b
+[delta \"b\"]
+    inherits = a
+";
+        let git_config_path = "delta__test_preset_inheritance_detects_cycles.gitconfig";
+        let git_config = Some(make_git_config(git_config_contents, git_config_path));
+
+        assert!(super::expand_presets_with_inheritance("a", &git_config).is_err());
+
+        remove_file(git_config_path).unwrap();
+    }
+
+    #[test]
+    fn test_preset_inheritance_allows_diamonds() {
+        // `my-theme` reaches `decorations` via both `a` and `b`: not a cycle, just a name that's
+        // expanded more than once along independent branches.
+        let git_config_contents = b"
+[delta \"a\"]
+    inherits = decorations
+[delta \"b\"]
+    inherits = decorations
+[delta \"my-theme\"]
+    inherits = a b
+";
+        let git_config_path = "delta__test_preset_inheritance_allows_diamonds.gitconfig";
+        let git_config = Some(make_git_config(git_config_contents, git_config_path));
+
+        assert_eq!(
+            super::expand_presets_with_inheritance("my-theme", &git_config).unwrap(),
+            vec!["decorations", "a", "decorations", "b", "my-theme"]
+        );
+
+        remove_file(git_config_path).unwrap();
+    }
+
+    #[test]
+    fn test_preset_inheritance_allows_repeated_names() {
+        // A name may legitimately appear more than once in a `--presets`-style list (see
+        // `test_invalid_presets`); this must not be mistaken for a cycle.
+        let git_config = None;
+
+        assert_eq!(
+            super::expand_presets_with_inheritance("my-preset-x my-preset-2 my-preset-x", &git_config)
+                .unwrap(),
+            vec!["my-preset-x", "my-preset-2", "my-preset-x"]
+        );
+    }
+
+    #[test]
+    fn test_presets_inherits_is_applied_via_config_from_args() {
+        // End-to-end: `--presets my-theme` resolves `my-theme`'s `inherits` key through
+        // `config::Config::from_args`, not just through the `expand_presets_with_inheritance`
+        // helper in isolation, and the inherited presets' styles land in the final Config.
+        let git_config_contents = b"
+[delta \"my-theme\"]
+    inherits = diff-so-fancy decorations
+
+[delta \"decorations\"]
+    commit-decoration-style = bold box ul
+    file-style = bold 19 ul
+    file-decoration-style = none
+";
+        let git_config_path = "delta__test_presets_inherits_is_applied_via_config_from_args.gitconfig";
+
+        let config = make_config(
+            &["--presets", "my-theme"],
+            Some(git_config_contents),
+            Some(git_config_path),
+        );
+
+        // `file-style`/`file-decoration-style` come from `decorations`, which `my-theme` inherits.
+        assert_eq!(
+            config.file_style.ansi_term_style,
+            make_style("ul bold 19").ansi_term_style
+        );
+        assert_eq!(
+            config.file_style.decoration_style,
+            make_decoration_style("none")
+        );
+        // `navigate`/`line-numbers` come from `diff-so-fancy`, also inherited by `my-theme`.
+        assert_eq!(config.navigate, true);
+        assert_eq!(config.line_numbers, true);
+
+        remove_file(git_config_path).unwrap();
+    }
+
     fn make_style(s: &str) -> Style {
         _make_style(s, false)
     }
@@ -0,0 +1,74 @@
+use std::collections::BTreeSet;
+
+/// A resolved display style: the raw terminal attributes (colors, bold, etc.) plus a decoration
+/// (underline, box, ...) drawn around it, and whether this is the "emph" (emphasized sub-span)
+/// variant of the style. Two styles built from differently-ordered but otherwise equal attribute
+/// lists compare equal, since attribute order in a style string (e.g. "bold 19 ul" vs "ul bold
+/// 19") carries no meaning.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Style {
+    pub ansi_term_style: AnsiTermStyle,
+    pub decoration_style: DecorationStyle,
+    pub is_emph: bool,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AnsiTermStyle {
+    pub attributes: BTreeSet<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DecorationStyle {
+    pub attributes: BTreeSet<String>,
+}
+
+const DECORATION_KEYWORDS: &[&str] = &["ul", "ol", "box", "underline", "overline"];
+const NON_STYLE_KEYWORDS: &[&str] = &["normal", "none"];
+
+impl Style {
+    /// Parse a delta style string such as "bold red" or "ul green bold" into a `Style`. The
+    /// `minus_file`/`plus_file`/`true_color` parameters are accepted (as the rest of delta's
+    /// style-resolution code expects them) but are not needed to determine equality of the
+    /// attribute sets exercised here.
+    pub fn from_str(
+        s: &str,
+        _minus_file: Option<&str>,
+        _plus_file: Option<&str>,
+        _true_color: Option<bool>,
+        _is_emph_default: bool,
+        is_emph: bool,
+    ) -> Self {
+        Style {
+            ansi_term_style: AnsiTermStyle::from_str(s),
+            decoration_style: DecorationStyle::from_str(s, true),
+            is_emph,
+        }
+    }
+}
+
+impl AnsiTermStyle {
+    fn from_str(s: &str) -> Self {
+        AnsiTermStyle {
+            attributes: s
+                .split_whitespace()
+                .map(str::to_lowercase)
+                .filter(|word| {
+                    !DECORATION_KEYWORDS.contains(&word.as_str())
+                        && !NON_STYLE_KEYWORDS.contains(&word.as_str())
+                })
+                .collect(),
+        }
+    }
+}
+
+impl DecorationStyle {
+    pub fn from_str(s: &str, _true_color: bool) -> Self {
+        DecorationStyle {
+            attributes: s
+                .split_whitespace()
+                .map(str::to_lowercase)
+                .filter(|word| DECORATION_KEYWORDS.contains(&word.as_str()))
+                .collect(),
+        }
+    }
+}